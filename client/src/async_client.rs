@@ -0,0 +1,163 @@
+#![cfg(feature = "async")]
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use nvidia_gpu_stats::metrics::Metrics;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::TcpStream;
+use tokio::sync::{oneshot, Mutex};
+
+const JSONRPC_VERSION: &str = "2.0";
+
+#[derive(Serialize)]
+struct RpcRequest<'a> {
+    jsonrpc: &'static str,
+    method: &'a str,
+    params: &'a Metrics,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    #[serde(default)]
+    id: Option<u64>,
+    #[serde(default)]
+    result: Option<Value>,
+    #[serde(default)]
+    error: Option<Value>,
+}
+
+type PendingCalls = Arc<Mutex<HashMap<u64, oneshot::Sender<Result<Value, Value>>>>>;
+
+/// Async counterpart to [`crate::client::Client`]: the same
+/// newline-delimited JSON-RPC 2.0 protocol, dispatched over tokio's
+/// non-blocking socket types for embedders already inside a tokio runtime.
+pub struct AsyncClient {
+    writer: OwnedWriteHalf,
+    next_id: AtomicU64,
+    pending: PendingCalls,
+}
+
+impl AsyncClient {
+    /// Connects to the backend's JSON-RPC socket at the given port, as
+    /// reported by `Launcher::start_async`.
+    pub async fn connect(port: i32) -> std::io::Result<Self> {
+        let stream = TcpStream::connect(("127.0.0.1", port as u16)).await?;
+        let (read_half, write_half) = stream.into_split();
+        let pending: PendingCalls = Arc::new(Mutex::new(HashMap::new()));
+
+        let reader_pending = Arc::clone(&pending);
+        tokio::spawn(Self::dispatch_loop(read_half, reader_pending));
+
+        Ok(AsyncClient {
+            writer: write_half,
+            next_id: AtomicU64::new(1),
+            pending,
+        })
+    }
+
+    /// Sends a metric update as a fire-and-forget `log` notification; the
+    /// backend is not expected to acknowledge it.
+    pub async fn log(&mut self, metrics: &Metrics) -> std::io::Result<()> {
+        self.send_request("log", metrics, None).await
+    }
+
+    /// Sends a `method` request and awaits the backend's reply, returning the
+    /// raw `result` or `error` payload.
+    pub async fn call(
+        &mut self,
+        method: &str,
+        metrics: &Metrics,
+    ) -> std::io::Result<Result<Value, Value>> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(id, tx);
+
+        self.send_request(method, metrics, Some(id)).await?;
+
+        Ok(rx.await.unwrap_or_else(|_| {
+            Err(Value::String(
+                "connection closed before a reply arrived".to_string(),
+            ))
+        }))
+    }
+
+    async fn send_request(
+        &mut self,
+        method: &str,
+        metrics: &Metrics,
+        id: Option<u64>,
+    ) -> std::io::Result<()> {
+        let request = RpcRequest {
+            jsonrpc: JSONRPC_VERSION,
+            method,
+            params: metrics,
+            id,
+        };
+        let mut line = serde_json::to_vec(&request)?;
+        line.push(b'\n');
+        self.writer.write_all(&line).await
+    }
+
+    async fn dispatch_loop(read_half: OwnedReadHalf, pending: PendingCalls) {
+        let mut lines = BufReader::new(read_half).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if line.is_empty() {
+                continue;
+            }
+            let response: RpcResponse = match serde_json::from_str(&line) {
+                Ok(response) => response,
+                Err(_) => continue,
+            };
+            let Some(id) = response.id else {
+                continue;
+            };
+            if let Some(tx) = pending.lock().await.remove(&id) {
+                let outcome = match (response.result, response.error) {
+                    (_, Some(error)) => Err(error),
+                    (Some(result), None) => Ok(result),
+                    (None, None) => Ok(Value::Null),
+                };
+                let _ = tx.send(outcome);
+            }
+        }
+        Self::fail_pending(&pending).await;
+    }
+
+    /// Fails every outstanding call once the read loop ends (EOF or socket
+    /// error), so `call()` always returns instead of awaiting a oneshot
+    /// receiver that nothing will ever send on again.
+    async fn fail_pending(pending: &PendingCalls) {
+        for (_, tx) in pending.lock().await.drain() {
+            let _ = tx.send(Err(Value::String(
+                "connection closed before a reply arrived".to_string(),
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn fail_pending_unblocks_every_outstanding_call() {
+        let pending: PendingCalls = Arc::new(Mutex::new(HashMap::new()));
+        let (tx1, rx1) = oneshot::channel();
+        let (tx2, rx2) = oneshot::channel();
+        pending.lock().await.insert(1, tx1);
+        pending.lock().await.insert(2, tx2);
+
+        AsyncClient::fail_pending(&pending).await;
+
+        assert!(rx1.await.unwrap().is_err());
+        assert!(rx2.await.unwrap().is_err());
+        assert!(pending.lock().await.is_empty());
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use nvidia_gpu_stats::metrics::Metrics;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const JSONRPC_VERSION: &str = "2.0";
+
+#[derive(Serialize)]
+struct RpcRequest<'a> {
+    jsonrpc: &'static str,
+    method: &'a str,
+    params: &'a Metrics,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    #[serde(default)]
+    id: Option<u64>,
+    #[serde(default)]
+    result: Option<Value>,
+    #[serde(default)]
+    error: Option<Value>,
+}
+
+type PendingCalls = Arc<Mutex<HashMap<u64, Sender<Result<Value, Value>>>>>;
+
+/// A connection to the wandb backend speaking newline-delimited JSON-RPC 2.0.
+///
+/// Requests carrying an `id` are matched against responses by a background
+/// reader thread; requests without an `id` (e.g. `log`) are fire-and-forget
+/// notifications that never wait on a reply.
+pub struct Client {
+    stream: TcpStream,
+    next_id: AtomicU64,
+    pending: PendingCalls,
+    reader_handle: Option<JoinHandle<()>>,
+}
+
+impl Client {
+    /// Connects to the backend's JSON-RPC socket at the given port, as
+    /// reported by `Launcher::start`.
+    pub fn connect(port: i32) -> std::io::Result<Self> {
+        let stream = TcpStream::connect(("127.0.0.1", port as u16))?;
+        let pending: PendingCalls = Arc::new(Mutex::new(HashMap::new()));
+
+        let reader_stream = stream.try_clone()?;
+        let reader_pending = Arc::clone(&pending);
+        let reader_handle = thread::spawn(move || Self::dispatch_loop(reader_stream, reader_pending));
+
+        Ok(Client {
+            stream,
+            next_id: AtomicU64::new(1),
+            pending,
+            reader_handle: Some(reader_handle),
+        })
+    }
+
+    /// Shuts down the socket and waits for the reader thread to exit.
+    ///
+    /// Without this, the reader thread's `try_clone()`'d socket keeps the
+    /// connection's read half alive independently of `stream`, so dropping a
+    /// `Client` would otherwise leave the thread blocked in `reader.lines()`
+    /// until the *remote* end closes — one leaked thread per reconnect.
+    pub fn shutdown(mut self) {
+        self.close();
+    }
+
+    fn close(&mut self) {
+        let _ = self.stream.shutdown(Shutdown::Both);
+        if let Some(handle) = self.reader_handle.take() {
+            let _ = handle.join();
+        }
+    }
+
+    /// Sends a metric update as a fire-and-forget `log` notification; the
+    /// backend is not expected to acknowledge it.
+    pub fn log(&mut self, metrics: &Metrics) -> std::io::Result<()> {
+        self.send_request("log", metrics, None)
+    }
+
+    /// Sends a `method` request and blocks until the backend replies, returning
+    /// the raw `result` or `error` payload.
+    pub fn call(&mut self, method: &str, metrics: &Metrics) -> std::io::Result<Result<Value, Value>> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = mpsc::channel();
+        self.pending.lock().unwrap().insert(id, tx);
+
+        self.send_request(method, metrics, Some(id))?;
+
+        Ok(rx.recv().unwrap_or_else(|_| {
+            Err(Value::String("connection closed before a reply arrived".to_string()))
+        }))
+    }
+
+    fn send_request(&mut self, method: &str, metrics: &Metrics, id: Option<u64>) -> std::io::Result<()> {
+        let request = RpcRequest {
+            jsonrpc: JSONRPC_VERSION,
+            method,
+            params: metrics,
+            id,
+        };
+        let mut line = serde_json::to_vec(&request)?;
+        line.push(b'\n');
+        self.stream.write_all(&line)
+    }
+
+    fn dispatch_loop(stream: TcpStream, pending: PendingCalls) {
+        let reader = BufReader::new(stream);
+        for line in reader.lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            if line.is_empty() {
+                continue;
+            }
+            Self::dispatch_line(&line, &pending);
+        }
+        Self::fail_pending(&pending);
+    }
+
+    /// Drains every outstanding call and fails it, so a reader thread that
+    /// exits on EOF or a socket error doesn't leave a `call()` blocked
+    /// forever on a `Sender` that nothing will ever use again.
+    fn fail_pending(pending: &PendingCalls) {
+        for (_, tx) in pending.lock().unwrap().drain() {
+            let _ = tx.send(Err(Value::String(
+                "connection closed before a reply arrived".to_string(),
+            )));
+        }
+    }
+
+    /// Parses one line of the backend's response stream and, if it carries
+    /// an `id` matching a pending call, delivers the result or error to the
+    /// waiting `call`. Lines that don't parse, or that carry no `id` (e.g.
+    /// acks of fire-and-forget notifications), are dropped on the floor.
+    fn dispatch_line(line: &str, pending: &PendingCalls) {
+        let response: RpcResponse = match serde_json::from_str(line) {
+            Ok(response) => response,
+            Err(_) => return,
+        };
+        let Some(id) = response.id else {
+            return;
+        };
+        if let Some(tx) = pending.lock().unwrap().remove(&id) {
+            let outcome = match (response.result, response.error) {
+                (_, Some(error)) => Err(error),
+                (Some(result), None) => Ok(result),
+                (None, None) => Ok(Value::Null),
+            };
+            let _ = tx.send(outcome);
+        }
+    }
+}
+
+impl Drop for Client {
+    /// Falls back to the same cleanup as `shutdown` for callers that let a
+    /// `Client` go out of scope instead of calling it explicitly.
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_with(id: u64) -> (PendingCalls, mpsc::Receiver<Result<Value, Value>>) {
+        let (tx, rx) = mpsc::channel();
+        let pending: PendingCalls = Arc::new(Mutex::new(HashMap::new()));
+        pending.lock().unwrap().insert(id, tx);
+        (pending, rx)
+    }
+
+    #[test]
+    fn dispatch_line_delivers_matching_result() {
+        let (pending, rx) = pending_with(1);
+        Client::dispatch_line(r#"{"jsonrpc":"2.0","id":1,"result":"ok"}"#, &pending);
+        assert_eq!(rx.recv().unwrap(), Ok(Value::String("ok".to_string())));
+    }
+
+    #[test]
+    fn dispatch_line_delivers_matching_error() {
+        let (pending, rx) = pending_with(1);
+        Client::dispatch_line(r#"{"jsonrpc":"2.0","id":1,"error":"boom"}"#, &pending);
+        assert_eq!(rx.recv().unwrap(), Err(Value::String("boom".to_string())));
+    }
+
+    #[test]
+    fn dispatch_line_ignores_notifications_without_id() {
+        let (pending, rx) = pending_with(1);
+        Client::dispatch_line(r#"{"jsonrpc":"2.0","result":"ok"}"#, &pending);
+        assert!(pending.lock().unwrap().contains_key(&1));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn dispatch_line_ignores_unmatched_id() {
+        let (pending, rx) = pending_with(1);
+        Client::dispatch_line(r#"{"jsonrpc":"2.0","id":2,"result":"ok"}"#, &pending);
+        assert!(pending.lock().unwrap().contains_key(&1));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn dispatch_line_ignores_malformed_json() {
+        let (pending, rx) = pending_with(1);
+        Client::dispatch_line("not json", &pending);
+        assert!(pending.lock().unwrap().contains_key(&1));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn fail_pending_unblocks_every_outstanding_call() {
+        let (pending, rx1) = pending_with(1);
+        let (tx2, rx2) = mpsc::channel();
+        pending.lock().unwrap().insert(2, tx2);
+
+        Client::fail_pending(&pending);
+
+        assert!(rx1.recv().unwrap().is_err());
+        assert!(rx2.recv().unwrap().is_err());
+        assert!(pending.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,169 @@
+use std::io::{BufRead, BufReader, Read};
+use std::net::TcpStream;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize)]
+struct RawEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    data: Value,
+}
+
+/// A server-pushed event delivered over the backend's event stream: run
+/// state changes, upload progress, or server-side alerts.
+#[derive(Debug, Clone)]
+pub enum ServerEvent {
+    RunStopped(Value),
+    UploadProgress(Value),
+    QuotaExceeded(Value),
+    Unknown { kind: String, data: Value },
+}
+
+impl From<RawEvent> for ServerEvent {
+    fn from(raw: RawEvent) -> Self {
+        match raw.kind.as_str() {
+            "run_stopped" => ServerEvent::RunStopped(raw.data),
+            "upload_progress" => ServerEvent::UploadProgress(raw.data),
+            "quota_exceeded" => ServerEvent::QuotaExceeded(raw.data),
+            _ => ServerEvent::Unknown {
+                kind: raw.kind,
+                data: raw.data,
+            },
+        }
+    }
+}
+
+/// A newline-framed reader over the backend's event socket. Iterating it
+/// yields one `ServerEvent` per frame, reassembling frames that arrive
+/// split across multiple reads and ending cleanly when the backend closes
+/// the connection.
+///
+/// Generic over the underlying reader so the frame-reassembly logic can be
+/// exercised in tests without a real socket; `connect` is the only way to
+/// get one wrapping a `TcpStream`.
+pub struct EventStream<R = TcpStream> {
+    reader: BufReader<R>,
+}
+
+impl EventStream<TcpStream> {
+    /// Connects to the backend's event socket at the given port, as
+    /// reported by `Launcher::start`.
+    pub fn connect(port: i32) -> std::io::Result<Self> {
+        let stream = TcpStream::connect(("127.0.0.1", port as u16))?;
+        Ok(EventStream {
+            reader: BufReader::new(stream),
+        })
+    }
+}
+
+impl<R: Read> Iterator for EventStream<R> {
+    type Item = std::io::Result<ServerEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) => {
+                    let line = line.trim();
+                    if line.is_empty() {
+                        continue;
+                    }
+                    let event = serde_json::from_str::<RawEvent>(line)
+                        .map(ServerEvent::from)
+                        .map_err(|error| {
+                            std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+                        });
+                    return Some(event);
+                }
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl<R: Read> EventStream<R> {
+        fn from_reader(reader: R) -> Self {
+            EventStream {
+                reader: BufReader::new(reader),
+            }
+        }
+    }
+
+    /// A `Read` source that only ever hands back a handful of bytes per
+    /// call, forcing `read_line` to make several reads to reassemble a
+    /// single frame.
+    struct Dribble(std::io::Cursor<Vec<u8>>);
+
+    impl Read for Dribble {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let limit = buf.len().min(3);
+            self.0.read(&mut buf[..limit])
+        }
+    }
+
+    #[test]
+    fn reassembles_a_frame_split_across_many_small_reads() {
+        let source = Dribble(std::io::Cursor::new(
+            br#"{"type":"run_stopped","data":{"reason":"done"}}"#.to_vec(),
+        ));
+        let mut stream = EventStream::from_reader(source);
+        let event = stream.next().unwrap().unwrap();
+        assert!(matches!(event, ServerEvent::RunStopped(_)));
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn yields_one_event_per_newline_delimited_frame() {
+        let source = std::io::Cursor::new(
+            b"{\"type\":\"upload_progress\",\"data\":1}\n{\"type\":\"quota_exceeded\"}\n".to_vec(),
+        );
+        let mut stream = EventStream::from_reader(source);
+        assert!(matches!(
+            stream.next().unwrap().unwrap(),
+            ServerEvent::UploadProgress(_)
+        ));
+        assert!(matches!(
+            stream.next().unwrap().unwrap(),
+            ServerEvent::QuotaExceeded(_)
+        ));
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn skips_blank_lines_between_frames() {
+        let source = std::io::Cursor::new(b"\n\n{\"type\":\"run_stopped\"}\n".to_vec());
+        let mut stream = EventStream::from_reader(source);
+        assert!(matches!(
+            stream.next().unwrap().unwrap(),
+            ServerEvent::RunStopped(_)
+        ));
+    }
+
+    #[test]
+    fn reports_unknown_event_kinds() {
+        let source = std::io::Cursor::new(b"{\"type\":\"weird\",\"data\":42}\n".to_vec());
+        let mut stream = EventStream::from_reader(source);
+        match stream.next().unwrap().unwrap() {
+            ServerEvent::Unknown { kind, data } => {
+                assert_eq!(kind, "weird");
+                assert_eq!(data, Value::from(42));
+            }
+            other => panic!("expected Unknown, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn errors_on_malformed_json() {
+        let source = std::io::Cursor::new(b"not json\n".to_vec());
+        let mut stream = EventStream::from_reader(source);
+        assert!(stream.next().unwrap().is_err());
+    }
+}
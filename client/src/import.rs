@@ -0,0 +1,89 @@
+use std::io::BufRead;
+
+use nvidia_gpu_stats::metrics::Metrics;
+
+use crate::client::Client;
+
+/// Outcome of an `import` run: how many records were delivered, and which
+/// line numbers (1-indexed) were malformed and skipped.
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    pub imported: usize,
+    pub failed_lines: Vec<usize>,
+}
+
+/// Streams newline-delimited JSON metric records from `reader` (a file or
+/// stdin) and feeds each through `client`, mirroring how users re-ingest
+/// exported runs. Reads one line at a time rather than loading the whole
+/// file into memory. A malformed line is skipped and its line number
+/// recorded instead of aborting the rest of the import.
+pub fn import<R: BufRead>(reader: R, client: &mut Client) -> std::io::Result<ImportSummary> {
+    let mut summary = ImportSummary::default();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            // A read error (e.g. a non-UTF-8 line) is itself a malformed
+            // record, not a reason to abort the batch and lose the summary
+            // accumulated so far.
+            Err(_) => {
+                summary.failed_lines.push(index + 1);
+                continue;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        match Metrics::from_json_line(&line) {
+            Ok(metrics) => {
+                client.log(&metrics)?;
+                summary.imported += 1;
+            }
+            Err(_) => summary.failed_lines.push(index + 1),
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::*;
+
+    /// Accepts a single connection and discards whatever it sends, so
+    /// `import` has somewhere to deliver successfully-parsed records.
+    fn spawn_sink() -> Client {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            if let Ok((stream, _)) = listener.accept() {
+                let _ = BufRead::lines(std::io::BufReader::new(stream)).count();
+            }
+        });
+        Client::connect(port as i32).unwrap()
+    }
+
+    #[test]
+    fn import_skips_malformed_lines_and_keeps_going() {
+        let mut client = spawn_sink();
+        let input = "{\"a\":1}\nnot json\n\n{\"b\":2}\n";
+        let summary = import(Cursor::new(input), &mut client).unwrap();
+        assert_eq!(summary.imported, 2);
+        assert_eq!(summary.failed_lines, vec![2]);
+    }
+
+    #[test]
+    fn import_skips_non_utf8_lines_without_aborting() {
+        let mut client = spawn_sink();
+        let mut input = b"{\"a\":1}\n".to_vec();
+        input.extend_from_slice(&[0xff, 0xfe, b'\n']);
+        input.extend_from_slice(b"{\"b\":2}\n");
+        let summary = import(Cursor::new(input), &mut client).unwrap();
+        assert_eq!(summary.imported, 2);
+        assert_eq!(summary.failed_lines, vec![2]);
+    }
+}
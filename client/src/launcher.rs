@@ -3,50 +3,200 @@ use std::process::Command;
 use tempfile::NamedTempFile;
 use std::fs;
 use std::{thread, time};
+use thiserror::Error;
 
 pub struct Launcher {
     pub command: String,
 }
 
-fn wait_for_port(port_filename: &str) -> i32 {
+#[derive(Error, Debug)]
+pub enum LauncherError {
+    #[error("failed to read port file {path}: {source}")]
+    ReadPortFile {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to parse port value {value:?} in port file {path}: {source}")]
+    ParsePort {
+        path: String,
+        value: String,
+        #[source]
+        source: std::num::ParseIntError,
+    },
+    #[error("failed to fork backend process")]
+    ForkFailed,
+    #[error("failed to spawn backend command {command:?}: {source}")]
+    SpawnCommand {
+        command: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to create port file: {0}")]
+    CreatePortFile(#[source] std::io::Error),
+    #[error("port file path {0:?} is not valid UTF-8")]
+    PortFilePathNotUtf8(std::path::PathBuf),
+}
+
+/// Looks for a completed `sock=<port>` line in the port file's contents,
+/// returning `None` while the file is still being written (no `EOF`
+/// sentinel yet).
+fn parse_port_file(port_filename: &str, contents: &str) -> Option<Result<i32, LauncherError>> {
+    let lines = contents.lines().collect::<Vec<_>>();
+    if lines.last().copied() != Some("EOF") {
+        return None;
+    }
+    for item in lines.iter() {
+        if let Some((param, val)) = item.split_once("=") {
+            if param == "sock" {
+                return Some(val.to_string().parse::<i32>().map_err(|source| {
+                    LauncherError::ParsePort {
+                        path: port_filename.to_string(),
+                        value: val.to_string(),
+                        source,
+                    }
+                }));
+            }
+        }
+    }
+    None
+}
+
+/// Creates the temp file the backend writes its port to, returning it
+/// together with its path as a `String` (the handle must outlive the path,
+/// since dropping it deletes the file).
+fn create_port_file() -> Result<(NamedTempFile, String), LauncherError> {
+    let port_file = NamedTempFile::new().map_err(LauncherError::CreatePortFile)?;
+    let port_filename = port_file
+        .path()
+        .to_str()
+        .ok_or_else(|| LauncherError::PortFilePathNotUtf8(port_file.path().to_path_buf()))?
+        .to_string();
+    Ok((port_file, port_filename))
+}
+
+fn wait_for_port(port_filename: &str) -> Result<i32, LauncherError> {
     let delay_time = time::Duration::from_millis(20);
     loop {
         thread::sleep(delay_time);
-        let contents = fs::read_to_string(port_filename)
-            .expect("Should have been able to read the file");
-        let lines = contents.lines().collect::<Vec<_>>();
-        if lines.last().copied() == Some("EOF") {
-            for item in lines.iter() {
-                match item.split_once("=") {
-                    None => continue,
-                    Some((param, val)) =>
-                        if param == "sock" {
-                            let my_int = val.to_string().parse::<i32>().unwrap();
-                            return my_int;
-                        },
-                }
+        let contents = match fs::read_to_string(port_filename) {
+            Ok(contents) => contents,
+            Err(source) => {
+                return Err(LauncherError::ReadPortFile {
+                    path: port_filename.to_string(),
+                    source,
+                });
             }
+        };
+        if let Some(result) = parse_port_file(port_filename, &contents) {
+            return result;
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+async fn wait_for_port_async(port_filename: &str) -> Result<i32, LauncherError> {
+    let delay_time = time::Duration::from_millis(20);
+    loop {
+        tokio::time::sleep(delay_time).await;
+        let contents = match tokio::fs::read_to_string(port_filename).await {
+            Ok(contents) => contents,
+            Err(source) => {
+                return Err(LauncherError::ReadPortFile {
+                    path: port_filename.to_string(),
+                    source,
+                });
+            }
+        };
+        if let Some(result) = parse_port_file(port_filename, &contents) {
+            return result;
         }
     }
 }
 
 impl Launcher {
-    pub fn start(&self) -> i32 {
-        let port_file = NamedTempFile::new().expect("tempfile should be created");
-        let port_filename = port_file.path().as_os_str().to_str().unwrap();
+    /// Forks and execs the backend command, then waits for it to report its
+    /// port.
+    ///
+    /// This calls the `fork` crate's raw `fork()`, which only duplicates the
+    /// calling thread — safe here because `start` runs before this crate
+    /// spawns any other threads (the `Client` reader thread, the `Logger`
+    /// writer thread, etc.) that could be mid-mutation of a lock at fork
+    /// time. Don't call `start` after spinning up any of those; use
+    /// `start_async` (which never forks) from a multi-threaded context
+    /// instead.
+    pub fn start(&self) -> Result<i32, LauncherError> {
+        let (_port_file, port_filename) = create_port_file()?;
         match fork() {
-            Ok(Fork::Parent(_child)) => {
-                let port = wait_for_port(port_filename);
-                return port;
-            },
+            Ok(Fork::Parent(_child)) => wait_for_port(&port_filename),
             Ok(Fork::Child) => {
-                let _command = Command::new(self.command.clone())
+                Command::new(self.command.clone())
                     .arg("--port-filename")
                     .arg(port_filename)
-                    .output();
+                    .output()
+                    .map_err(|source| LauncherError::SpawnCommand {
+                        command: self.command.clone(),
+                        source,
+                    })?;
+                Ok(0)
             },
-            Err(_) => println!("Fork failed"),
+            Err(_) => Err(LauncherError::ForkFailed),
         }
-        0
     }
-}
\ No newline at end of file
+
+    /// Async counterpart to [`Launcher::start`]: awaits the port file
+    /// becoming ready with non-blocking file reads and a timer instead of
+    /// busy-sleeping a whole thread, so callers already inside a tokio
+    /// runtime don't need to spawn one.
+    ///
+    /// Deliberately does not use `start`'s `fork()` strategy: POSIX `fork()`
+    /// only duplicates the calling thread, so forking from inside a
+    /// multi-threaded tokio runtime would drop every other worker thread in
+    /// the child along with any lock it happened to hold at that instant
+    /// (including this crate's own `OFFLINE_DB` mutex, or tokio's internal
+    /// runtime state) — a recipe for a nondeterministic deadlock or
+    /// corrupted runtime in the child. Instead this spawns the backend
+    /// command as an ordinary child process via `tokio::process::Command`,
+    /// which execs without duplicating the caller's address space.
+    #[cfg(feature = "async")]
+    pub async fn start_async(&self) -> Result<i32, LauncherError> {
+        let (_port_file, port_filename) = create_port_file()?;
+        tokio::process::Command::new(&self.command)
+            .arg("--port-filename")
+            .arg(&port_filename)
+            .spawn()
+            .map_err(|source| LauncherError::SpawnCommand {
+                command: self.command.clone(),
+                source,
+            })?;
+        wait_for_port_async(&port_filename).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_port_file_waits_for_eof_sentinel() {
+        assert!(parse_port_file("port", "sock=1234").is_none());
+    }
+
+    #[test]
+    fn parse_port_file_reads_sock_once_complete() {
+        let result = parse_port_file("port", "sock=1234\nEOF").unwrap();
+        assert_eq!(result.unwrap(), 1234);
+    }
+
+    #[test]
+    fn parse_port_file_reports_unparseable_port() {
+        let result = parse_port_file("port", "sock=not-a-number\nEOF").unwrap();
+        assert!(matches!(result, Err(LauncherError::ParsePort { .. })));
+    }
+
+    #[test]
+    fn parse_port_file_ignores_unrelated_lines() {
+        let result = parse_port_file("port", "pid=1\nsock=1234\nEOF").unwrap();
+        assert_eq!(result.unwrap(), 1234);
+    }
+}
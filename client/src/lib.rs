@@ -0,0 +1,8 @@
+#[cfg(feature = "async")]
+pub mod async_client;
+pub mod client;
+pub mod events;
+pub mod import;
+pub mod launcher;
+pub mod logger;
+pub mod offline;
@@ -0,0 +1,212 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use nvidia_gpu_stats::metrics::Metrics;
+
+/// Reported by the writer thread when it fails to open or append to its
+/// file, so a caller can surface the failure however it likes instead of
+/// the thread panicking, and once more (`Stopped`) after it has drained the
+/// channel and flushed the file.
+#[derive(Debug)]
+pub enum LoggerStatus {
+    WriteError(String),
+    Stopped,
+}
+
+/// A non-blocking JSONL logger: `log` pushes metrics onto a channel and
+/// returns immediately, while a background thread owns the file handle and
+/// appends one JSON object per line.
+pub struct Logger {
+    sender: Sender<Metrics>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Logger {
+    /// Spawns the writer thread targeting `path`, creating parent
+    /// directories if needed. Returns the logger and a channel that reports
+    /// write failures as they happen.
+    pub fn spawn(path: impl Into<PathBuf>) -> (Self, Receiver<LoggerStatus>) {
+        let path = path.into();
+        let (tx, rx) = mpsc::channel::<Metrics>();
+        let (status_tx, status_rx) = mpsc::channel();
+
+        let handle = thread::spawn(move || Self::run(path, rx, status_tx));
+
+        (
+            Logger {
+                sender: tx,
+                handle: Some(handle),
+            },
+            status_rx,
+        )
+    }
+
+    /// Returns a cloneable handle that can be shared across threads to push
+    /// metrics onto this logger without blocking on disk I/O.
+    pub fn sender(&self) -> Sender<Metrics> {
+        self.sender.clone()
+    }
+
+    /// Queues `metrics` for the writer thread.
+    pub fn log(&self, metrics: Metrics) {
+        let _ = self.sender.send(metrics);
+    }
+
+    /// Drops this logger's sender and waits for the writer thread to drain
+    /// its queue and flush the file. Clones of `sender()` held elsewhere
+    /// keep the channel open until they too are dropped.
+    pub fn shutdown(self) {
+        let Logger { sender, handle } = self;
+        drop(sender);
+        if let Some(handle) = handle {
+            let _ = handle.join();
+        }
+    }
+
+    fn run(path: PathBuf, rx: Receiver<Metrics>, status: Sender<LoggerStatus>) {
+        let mut file = match Self::open(&path, &status) {
+            Some(file) => file,
+            None => return,
+        };
+
+        for mut metrics in rx.iter() {
+            if !metrics.has_metric("_timestamp") {
+                metrics.add_timestamp(Self::now());
+            }
+            if let Err(error) = Self::write_line(&mut file, &metrics) {
+                let _ = status.send(LoggerStatus::WriteError(error.to_string()));
+            }
+        }
+
+        let _ = file.flush();
+        let _ = status.send(LoggerStatus::Stopped);
+    }
+
+    fn open(path: &Path, status: &Sender<LoggerStatus>) -> Option<File> {
+        if let Some(parent) = path.parent() {
+            if let Err(error) = fs::create_dir_all(parent) {
+                let _ = status.send(LoggerStatus::WriteError(error.to_string()));
+                return None;
+            }
+        }
+        match OpenOptions::new().create(true).append(true).open(path) {
+            Ok(file) => Some(file),
+            Err(error) => {
+                let _ = status.send(LoggerStatus::WriteError(error.to_string()));
+                None
+            }
+        }
+    }
+
+    fn write_line(file: &mut File, metrics: &Metrics) -> std::io::Result<()> {
+        let json = metrics
+            .to_json()
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+        writeln!(file, "{}", json)
+    }
+
+    fn now() -> f64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader};
+
+    use super::*;
+
+    #[test]
+    fn logs_one_json_object_per_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.jsonl");
+        let (logger, _status) = Logger::spawn(&path);
+
+        let mut first = Metrics::new();
+        first.add_metric("loss", 1.0);
+        let mut second = Metrics::new();
+        second.add_metric("loss", 0.5);
+        logger.log(first);
+        logger.log(second);
+        logger.shutdown();
+
+        let lines: Vec<String> = BufReader::new(File::open(&path).unwrap())
+            .lines()
+            .collect::<std::io::Result<_>>()
+            .unwrap();
+        assert_eq!(lines.len(), 2);
+        for line in &lines {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert!(value.get("loss").is_some());
+        }
+    }
+
+    #[test]
+    fn stamps_a_timestamp_when_one_is_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.jsonl");
+        let (logger, _status) = Logger::spawn(&path);
+
+        logger.log(Metrics::new());
+        logger.shutdown();
+
+        let line = BufReader::new(File::open(&path).unwrap())
+            .lines()
+            .next()
+            .unwrap()
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert!(value.get("_timestamp").is_some());
+    }
+
+    #[test]
+    fn preserves_an_explicit_timestamp() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.jsonl");
+        let (logger, _status) = Logger::spawn(&path);
+
+        let mut metrics = Metrics::new();
+        metrics.add_timestamp(42.0);
+        logger.log(metrics);
+        logger.shutdown();
+
+        let line = BufReader::new(File::open(&path).unwrap())
+            .lines()
+            .next()
+            .unwrap()
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value.get("_timestamp").unwrap().as_f64().unwrap(), 42.0);
+    }
+
+    #[test]
+    fn shutdown_reports_stopped_after_draining() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.jsonl");
+        let (logger, status) = Logger::spawn(&path);
+
+        logger.log(Metrics::new());
+        logger.shutdown();
+
+        assert!(matches!(status.recv().unwrap(), LoggerStatus::Stopped));
+    }
+
+    #[test]
+    fn creates_parent_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("run.jsonl");
+        let (logger, _status) = Logger::spawn(&path);
+
+        logger.log(Metrics::new());
+        logger.shutdown();
+
+        assert!(path.exists());
+    }
+}
@@ -0,0 +1,182 @@
+use std::sync::Mutex;
+
+use nvidia_gpu_stats::metrics::Metrics;
+use thiserror::Error;
+
+use crate::client::Client;
+
+const QUEUE_TREE: &str = "offline_metrics_queue";
+const REJECTED_TREE: &str = "offline_metrics_rejected";
+const DB_PATH_ENV: &str = "WANDB_OFFLINE_DB";
+const DEFAULT_DB_PATH: &str = "wandb-offline.sled";
+
+static OFFLINE_DB: Mutex<Option<sled::Db>> = Mutex::new(None);
+
+#[derive(Error, Debug)]
+pub enum OfflineError {
+    #[error("failed to open offline store: {0}")]
+    OpenStore(#[source] sled::Error),
+    #[error("failed to serialize metrics for offline storage: {0}")]
+    Serialize(#[source] serde_json::Error),
+    #[error("offline store I/O error: {0}")]
+    Store(#[source] sled::Error),
+    #[error("failed to deliver buffered metrics to the backend: {0}")]
+    Delivery(#[source] std::io::Error),
+}
+
+/// Returns the process-wide offline store, opening it on first use.
+///
+/// Guarded by a `Mutex` (rather than `OnceLock::get_or_init`) so that two
+/// threads racing to open the store on the first call can't both call
+/// `sled::open` concurrently: sled's directory lock would hand the loser a
+/// spurious `OpenStore` error even though the winner's handle is about to be
+/// published. `sled::Db` is a cheap `Arc`-backed handle, so cloning it out of
+/// the mutex is fine.
+fn offline_db() -> Result<sled::Db, OfflineError> {
+    let mut guard = OFFLINE_DB.lock().unwrap();
+    if let Some(db) = guard.as_ref() {
+        return Ok(db.clone());
+    }
+    let path = std::env::var(DB_PATH_ENV).unwrap_or_else(|_| DEFAULT_DB_PATH.to_string());
+    let db = sled::open(path).map_err(OfflineError::OpenStore)?;
+    *guard = Some(db.clone());
+    Ok(db)
+}
+
+/// Buffers `metrics` to disk for later delivery, for use when the backend
+/// socket from `Launcher` is unreachable.
+pub fn enqueue(metrics: &Metrics) -> Result<(), OfflineError> {
+    let db = offline_db()?;
+    let tree = db.open_tree(QUEUE_TREE).map_err(OfflineError::Store)?;
+    let id = db.generate_id().map_err(OfflineError::Store)?;
+    let value = metrics.to_json().map_err(OfflineError::Serialize)?;
+    tree.insert(id.to_be_bytes(), value.as_bytes())
+        .map_err(OfflineError::Store)?;
+    Ok(())
+}
+
+/// Outcome of a `flush_to` run: how many buffered records were delivered,
+/// and the sequence numbers of any the backend permanently rejected.
+#[derive(Debug, Default)]
+pub struct FlushSummary {
+    pub flushed: usize,
+    pub rejected: Vec<u64>,
+}
+
+/// Replays every buffered record over `client` in the order it was
+/// enqueued, removing each one only once the backend has acknowledged it.
+///
+/// A connectivity failure (the backend goes away mid-replay) aborts the
+/// flush immediately, leaving that record and everything after it in place
+/// so a later call can resume where this one left off. A record the backend
+/// actively rejects is different: it will never succeed on retry, so rather
+/// than head-of-line-blocking every record enqueued after it, it's moved to
+/// a dead-letter tree (`offline_metrics_rejected`) and the flush continues.
+pub fn flush_to(client: &mut Client) -> Result<FlushSummary, OfflineError> {
+    let db = offline_db()?;
+    let tree = db.open_tree(QUEUE_TREE).map_err(OfflineError::Store)?;
+    let rejected_tree = db.open_tree(REJECTED_TREE).map_err(OfflineError::Store)?;
+
+    let mut summary = FlushSummary::default();
+    for entry in tree.iter() {
+        let (key, value) = entry.map_err(OfflineError::Store)?;
+        let metrics: Metrics = serde_json::from_slice(&value).map_err(OfflineError::Serialize)?;
+
+        let outcome = client.call("log", &metrics).map_err(OfflineError::Delivery)?;
+        match outcome {
+            Ok(_) => {
+                tree.remove(&key).map_err(OfflineError::Store)?;
+                summary.flushed += 1;
+            }
+            Err(_) => {
+                rejected_tree.insert(&key, value).map_err(OfflineError::Store)?;
+                tree.remove(&key).map_err(OfflineError::Store)?;
+                let id = u64::from_be_bytes(key.as_ref().try_into().unwrap());
+                summary.rejected.push(id);
+            }
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::*;
+
+    /// Accepts a single connection and replies to every `log` call, acking
+    /// it unless its `loss` metric is `reject`, so `flush_to` has a live
+    /// backend to replay the queue against (and a way to trigger a
+    /// backend-side rejection on demand).
+    fn spawn_sink() -> Client {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            use std::io::Write;
+            if let Ok((stream, _)) = listener.accept() {
+                let reader = std::io::BufReader::new(stream.try_clone().unwrap());
+                let mut writer = stream;
+                for line in reader.lines() {
+                    let Ok(line) = line else { break };
+                    let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+                    let Some(id) = request.get("id") else { continue };
+                    let response = if request["params"]["loss"] == "reject" {
+                        serde_json::json!({"jsonrpc": "2.0", "id": id, "error": "rejected"})
+                    } else {
+                        serde_json::json!({"jsonrpc": "2.0", "id": id, "result": "ok"})
+                    };
+                    let mut bytes = serde_json::to_vec(&response).unwrap();
+                    bytes.push(b'\n');
+                    if writer.write_all(&bytes).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        Client::connect(port as i32).unwrap()
+    }
+
+    /// `OFFLINE_DB` is a process-wide global, opened on first use, so this
+    /// is the only test in the crate allowed to touch it: point it at a
+    /// fresh temp dir before anything else does.
+    fn offline_tree_for_test() -> sled::Tree {
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var(DB_PATH_ENV, dir.path());
+        let db = offline_db().unwrap();
+        std::mem::forget(dir);
+        db.open_tree(QUEUE_TREE).unwrap()
+    }
+
+    /// `OFFLINE_DB` is process-global, so this single test exercises both
+    /// the happy path and the rejection path rather than risking two tests
+    /// racing over the same queue/dead-letter trees.
+    #[test]
+    fn flush_to_drains_accepted_records_and_quarantines_rejected_ones() {
+        let tree = offline_tree_for_test();
+        let db = offline_db().unwrap();
+        let rejected_tree = db.open_tree(REJECTED_TREE).unwrap();
+        tree.clear().unwrap();
+        rejected_tree.clear().unwrap();
+
+        let mut first = Metrics::new();
+        first.add_metric("loss", 1.0);
+        let mut bad = Metrics::new();
+        bad.add_metric("loss", "reject");
+        let mut last = Metrics::new();
+        last.add_metric("loss", 0.5);
+        enqueue(&first).unwrap();
+        enqueue(&bad).unwrap();
+        enqueue(&last).unwrap();
+
+        let mut client = spawn_sink();
+        let summary = flush_to(&mut client).unwrap();
+
+        assert_eq!(summary.flushed, 2);
+        assert_eq!(summary.rejected.len(), 1);
+        assert_eq!(tree.iter().count(), 0);
+        assert_eq!(rejected_tree.iter().count(), 1);
+    }
+}
@@ -1,7 +1,7 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 pub struct Metrics {
     #[serde(flatten)]
     metrics: BTreeMap<String, serde_json::Value>,
@@ -22,9 +22,23 @@ impl Metrics {
         self.add_metric("_timestamp", timestamp);
     }
 
+    pub fn has_metric(&self, key: &str) -> bool {
+        self.metrics.contains_key(key)
+    }
+
     pub fn print_json(&self) -> Result<(), serde_json::Error> {
-        let json_output = serde_json::to_string(&self.metrics)?;
-        println!("{}", json_output);
+        println!("{}", self.to_json()?);
         Ok(())
     }
+
+    /// Serializes the metrics to a single JSON object, without printing it.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self.metrics)
+    }
+
+    /// Rebuilds a `Metrics` from a single line of serialized JSON, as
+    /// produced by `to_json` or the JSONL logger.
+    pub fn from_json_line(line: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(line)
+    }
 }